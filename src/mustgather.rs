@@ -3,29 +3,465 @@
 
 use crate::prelude::*;
 use crate::resources::Node;
+use flate2::read::GzDecoder;
+use fs2::FileExt;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
+use std::fs::File;
 use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Identifies a resource collection by API group, kind, and namespace. An
+/// empty namespace means the resource is cluster-scoped.
+pub type ResourceKey = (String, String, String);
+
+/// The resolved must-gather root plus a lazily-built index of which
+/// resource manifests are present under it. Resource accessors go through
+/// here so the filesystem is only walked once, on first use.
+struct Context {
+    root: PathBuf,
+    index: OnceCell<(HashMap<ResourceKey, Vec<PathBuf>>, Vec<anyhow::Error>)>,
+}
+
+impl Context {
+    fn new(root: PathBuf) -> Context {
+        Context {
+            root,
+            index: OnceCell::new(),
+        }
+    }
+
+    /// The `(group, kind, namespace)` -> manifest file paths index, and any
+    /// directories that couldn't be read while building it. Built on first
+    /// use by walking `namespaces/` and `cluster-scoped-resources/`.
+    fn indexed(&self) -> &(HashMap<ResourceKey, Vec<PathBuf>>, Vec<anyhow::Error>) {
+        self.index.get_or_init(|| build_index(&self.root))
+    }
+
+    fn index(&self) -> &HashMap<ResourceKey, Vec<PathBuf>> {
+        &self.indexed().0
+    }
+
+    /// The manifest file paths for a given `(namespace, kind, group)`, or an
+    /// empty slice if none are present.
+    fn manifests(&self, namespace: &str, kind: &str, group: &str) -> &[PathBuf] {
+        self.index()
+            .get(&(group.to_string(), kind.to_string(), namespace.to_string()))
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Recursively walk `dir`, invoking `visit` on every file found. Hidden
+/// ("dot") directories are skipped, except `dir` itself, so a must-gather
+/// root that happens to live under a dot-directory is still walked.
+/// Directories that fail to read are reported in `warnings` rather than
+/// aborting the walk.
+fn walk(dir: &Path, visit: &mut dyn FnMut(&Path), warnings: &mut Vec<anyhow::Error>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            warnings.push(anyhow::anyhow!("reading directory {}: {}", dir.display(), e));
+            return;
+        }
+    };
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                warnings.push(anyhow::anyhow!("reading entry in {}: {}", dir.display(), e));
+                continue;
+            }
+        };
+        let path = entry.path();
+        if path.is_dir() {
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with('.'))
+                .unwrap_or(false);
+            if !hidden {
+                walk(&path, visit, warnings);
+            }
+        } else {
+            visit(&path);
+        }
+    }
+}
+
+/// Build the `(group, kind, namespace)` -> manifest file paths index by
+/// recursively walking `namespaces/<ns>/<group>/<kind>/*.yaml` and
+/// `cluster-scoped-resources/<group>/<kind>/*.yaml` under `root`. This finds
+/// every resource kind present, including ones this crate has no typed
+/// wrapper for.
+fn build_index(root: &Path) -> (HashMap<ResourceKey, Vec<PathBuf>>, Vec<anyhow::Error>) {
+    let mut index: HashMap<ResourceKey, Vec<PathBuf>> = HashMap::new();
+    let mut warnings = Vec::new();
+
+    let csr = root.join("cluster-scoped-resources");
+    if fs::read_dir(&csr).is_ok() {
+        walk(
+            &csr,
+            &mut |path| {
+                if let Some(key) = resource_key(&csr, path, "") {
+                    index.entry(key).or_default().push(path.to_path_buf());
+                }
+            },
+            &mut warnings,
+        );
+    }
+
+    let namespaces = root.join("namespaces");
+    if let Ok(nsdirs) = fs::read_dir(&namespaces) {
+        for ns_entry in nsdirs.flatten().filter(|e| e.path().is_dir()) {
+            let namespace = ns_entry.file_name().to_string_lossy().to_string();
+            let ns_path = ns_entry.path();
+            walk(
+                &ns_path,
+                &mut |path| {
+                    if let Some(key) = resource_key(&ns_path, path, &namespace) {
+                        index.entry(key).or_default().push(path.to_path_buf());
+                    }
+                },
+                &mut warnings,
+            );
+        }
+    }
+
+    (index, warnings)
+}
+
+/// If `path` is a `.yaml` manifest found directly under `<base>/<group>/<kind>/`,
+/// return the resource key it belongs to.
+fn resource_key(base: &Path, path: &Path, namespace: &str) -> Option<ResourceKey> {
+    if path.extension().map(|e| e != "yaml").unwrap_or(true) {
+        return None;
+    }
+    let rel = path.strip_prefix(base).ok()?;
+    let comps: Vec<_> = rel.components().collect();
+    if comps.len() != 3 {
+        return None;
+    }
+    let group = comps[0].as_os_str().to_string_lossy().to_string();
+    let kind = comps[1].as_os_str().to_string_lossy().to_string();
+    Some((group, kind, namespace.to_string()))
+}
 
 pub struct MustGather {
     pub title: String,
     pub version: String,
-    pub nodes: Vec<Node>,
+    ctx: Context,
+    // Private so access goes through `nodes()`, which parses and caches lazily.
+    nodes: OnceCell<(Vec<Node>, Vec<anyhow::Error>)>,
+    resources: OnceCell<(HashMap<ResourceKey, Vec<Manifest>>, Vec<anyhow::Error>)>,
+    // Diagnostics restored from a cache hit, where the original `nodes`/
+    // `resources` OnceCells that produced them no longer exist to query.
+    // Empty for a freshly-parsed MustGather.
+    cached_warnings: Vec<anyhow::Error>,
+    // Kept alive for as long as the MustGather is, since `ctx.root` points
+    // into it when the input was a compressed archive; never read after `build`.
+    _tempdir: Option<TempDir>,
 }
 
 impl MustGather {
-    /// Build a MustGather from a path to a directory containing the root.
+    /// Build a MustGather from a path to a directory containing the root, or
+    /// to a `.tar`, `.tar.gz`, or `.tgz` archive containing one.
     pub fn from(path: String) -> Result<MustGather> {
+        Self::from_with_cache(path, CacheMode::Disabled)
+    }
+
+    /// Like `from`, but consults (and populates) a persistent on-disk cache
+    /// of the parsed model, keyed by a fingerprint of `path`, per `mode`.
+    /// On a cache hit, repeat inspections of the same must-gather skip
+    /// building the resource index and parsing manifests. For a directory
+    /// input, computing the fingerprint still walks every file under it.
+    pub fn from_with_cache(path: String, mode: CacheMode) -> Result<MustGather> {
+        let dir = match &mode {
+            CacheMode::Disabled => return Self::build(path),
+            CacheMode::Enabled { dir } | CacheMode::Invalidate { dir } => dir.clone(),
+        };
+
+        let key = match fingerprint(&path) {
+            Ok(key) => key,
+            // Can't compute a cache key, but `CacheMode::Disabled` would still
+            // process this input fine; don't let enabling the cache turn a
+            // working must-gather into a hard failure.
+            Err(_) => return Self::build(path),
+        };
+        if !matches!(mode, CacheMode::Invalidate { .. }) {
+            if let Some(cached) = cache_load(&dir, &key)? {
+                return Ok(cached.into_mustgather());
+            }
+        }
+
+        let mustgather = Self::build(path)?;
+        cache_store(&dir, &key, &mustgather)?;
+        Ok(mustgather)
+    }
+
+    fn build(path: String) -> Result<MustGather> {
+        let (path, tempdir) = match extract_archive(&path)? {
+            Some(tempdir) => (String::from(tempdir.path().to_str().unwrap()), Some(tempdir)),
+            None => (path, None),
+        };
         let path = find_must_gather_root(path)?;
         let title = String::from(path.file_name().unwrap().to_str().unwrap());
-        let version = get_cluster_version(&path);
-        let nodes = get_nodes(&path);
+        let ctx = Context::new(path);
+        let version = get_cluster_version(&ctx);
 
         Ok(MustGather {
             title,
             version,
-            nodes,
+            ctx,
+            nodes: OnceCell::new(),
+            resources: OnceCell::new(),
+            cached_warnings: Vec::new(),
+            _tempdir: tempdir,
         })
     }
+
+    /// Get all the Nodes in the cluster, parsing and caching them on first
+    /// access.
+    pub fn nodes(&self) -> &Vec<Node> {
+        &self.nodes.get_or_init(|| get_nodes(&self.ctx)).0
+    }
+
+    /// Every resource present in the must-gather, grouped by
+    /// `(group, kind, namespace)`, including kinds this crate has no typed
+    /// wrapper for. Parsed and cached on first access.
+    pub fn resources(&self) -> &HashMap<ResourceKey, Vec<Manifest>> {
+        &self
+            .resources
+            .get_or_init(|| {
+                let mut warnings = Vec::new();
+                let resources = self
+                    .ctx
+                    .index()
+                    .iter()
+                    .map(|(key, paths)| {
+                        let manifests = paths
+                            .iter()
+                            .filter_map(|p| match Manifest::from(p.clone()) {
+                                Ok(m) => Some(m),
+                                Err(e) => {
+                                    warnings.push(e.context(format!("parsing {}", p.display())));
+                                    None
+                                }
+                            })
+                            .collect();
+                        (key.clone(), manifests)
+                    })
+                    .collect();
+                (resources, warnings)
+            })
+            .0
+    }
+
+    /// Manifests that failed to parse, or directories that failed to read,
+    /// while building `nodes()` and `resources()`. Empty if neither has been
+    /// called yet, or if nothing went wrong. For a `MustGather` restored
+    /// from the on-disk cache, these are the diagnostics from the original
+    /// parse that produced the cache entry.
+    pub fn warnings(&self) -> Vec<&anyhow::Error> {
+        let mut warnings: Vec<&anyhow::Error> = self.cached_warnings.iter().collect();
+        if let Some((_, w)) = self.nodes.get() {
+            warnings.extend(w);
+        }
+        if let Some((_, w)) = self.resources.get() {
+            warnings.extend(w);
+        }
+        if let Some((_, w)) = self.ctx.index.get() {
+            warnings.extend(w);
+        }
+        warnings
+    }
+}
+
+/// If `path` looks like a `.tar.gz`/`.tgz` or plain `.tar` archive, report
+/// whether it's gzipped. Returns `None` if `path` doesn't look like a tar
+/// archive at all.
+fn archive_encoding(path: &str) -> Option<bool> {
+    let lower = path.to_lowercase();
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        Some(true)
+    } else if lower.ends_with(".tar") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// If `path` looks like a `.tar`, `.tar.gz`, or `.tgz` archive, stream it out
+/// into a fresh temporary directory and return that directory. Otherwise
+/// return `None`, so the caller treats `path` as an already-extracted tree.
+///
+/// The returned `TempDir` removes its contents on drop, so callers must hold
+/// on to it for as long as the extracted path is in use.
+fn extract_archive(path: &str) -> Result<Option<TempDir>> {
+    let gzipped = match archive_encoding(path) {
+        Some(gzipped) => gzipped,
+        None => return Ok(None),
+    };
+
+    let tempdir = tempfile::Builder::new().prefix("camgi-").tempdir()?;
+    let file = File::open(path)?;
+    if gzipped {
+        tar::Archive::new(GzDecoder::new(file)).unpack(tempdir.path())?;
+    } else {
+        tar::Archive::new(file).unpack(tempdir.path())?;
+    }
+    Ok(Some(tempdir))
+}
+
+/// Controls whether `MustGather::from_with_cache` consults and populates a
+/// persistent on-disk cache of the parsed model.
+pub enum CacheMode {
+    /// Always parse from scratch; what `MustGather::from` uses.
+    Disabled,
+    /// Look in `dir` for a cached model matching the input's fingerprint,
+    /// parsing and writing one back on a miss.
+    Enabled { dir: PathBuf },
+    /// Like `Enabled`, but ignore any existing cache entry and overwrite it.
+    Invalidate { dir: PathBuf },
+}
+
+/// Snapshot of a parsed `MustGather`, persisted to the cache database so a
+/// repeat inspection of the same input can skip indexing and parsing.
+/// Requires `Node` and `Manifest` to derive `Serialize`/`Deserialize` in
+/// `resources.rs`; both already round-trip through `serde_yaml` to parse
+/// manifests, so this only needs their existing derives extended to serde's
+/// traits in general, not a new ability.
+/// `resources` is a `Vec` of pairs rather than a `HashMap` because its key
+/// is a tuple, which `serde_json` can't use as an object key. `warnings` is
+/// the formatted output of `MustGather::warnings()` at cache-write time,
+/// since `anyhow::Error` itself isn't `Serialize`.
+#[derive(Serialize, Deserialize)]
+struct CachedModel {
+    title: String,
+    version: String,
+    nodes: Vec<Node>,
+    resources: Vec<(ResourceKey, Vec<Manifest>)>,
+    warnings: Vec<String>,
+}
+
+impl CachedModel {
+    fn from_mustgather(mg: &MustGather) -> CachedModel {
+        CachedModel {
+            title: mg.title.clone(),
+            version: mg.version.clone(),
+            nodes: mg.nodes().clone(),
+            resources: mg.resources().clone().into_iter().collect(),
+            warnings: mg.warnings().iter().map(|e| format!("{:#}", e)).collect(),
+        }
+    }
+
+    fn into_mustgather(self) -> MustGather {
+        let nodes = OnceCell::new();
+        nodes.set((self.nodes, Vec::new())).ok();
+        let resources = OnceCell::new();
+        resources
+            .set((self.resources.into_iter().collect(), Vec::new()))
+            .ok();
+
+        MustGather {
+            title: self.title,
+            version: self.version,
+            ctx: Context::new(PathBuf::new()),
+            nodes,
+            resources,
+            cached_warnings: self.warnings.into_iter().map(anyhow::Error::msg).collect(),
+            _tempdir: None,
+        }
+    }
+}
+
+/// A stable fingerprint of the must-gather input, used as the cache key:
+/// the digest of the archive file itself, or for an already-extracted
+/// directory, a hash of its `version` file plus the mtimes of every file
+/// under it.
+fn fingerprint(path: &str) -> Result<String> {
+    let mut hasher = Sha256::new();
+
+    if archive_encoding(path).is_some() {
+        std::io::copy(&mut File::open(path)?, &mut hasher)?;
+        return Ok(format!("{:x}", hasher.finalize()));
+    }
+
+    let root = PathBuf::from(path);
+    if let Ok(version) = fs::read(root.join("version")) {
+        hasher.update(&version);
+    }
+    let mut mtimes = Vec::new();
+    let mut warnings = Vec::new();
+    walk(
+        &root,
+        &mut |p| {
+            if let Ok(modified) = fs::metadata(p).and_then(|m| m.modified()) {
+                mtimes.push((p.to_path_buf(), modified));
+            }
+        },
+        &mut warnings,
+    );
+    // An unreadable directory means the fingerprint can't see everything
+    // under `root`, so it could alias an unrelated input instead of missing
+    // the cache; refuse to guess rather than risk a false cache hit.
+    if let Some(w) = warnings.into_iter().next() {
+        return Err(w.context(format!("fingerprinting {}", root.display())));
+    }
+    mtimes.sort();
+    for (p, modified) in mtimes {
+        hasher.update(p.to_string_lossy().as_bytes());
+        if let Ok(since_epoch) = modified.duration_since(std::time::UNIX_EPOCH) {
+            hasher.update(since_epoch.as_secs().to_le_bytes());
+        }
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn cache_db_path(dir: &Path) -> PathBuf {
+    dir.join("camgi-cache.json")
+}
+
+fn cache_load(dir: &Path, key: &str) -> Result<Option<CachedModel>> {
+    let data = match fs::read(cache_db_path(dir)) {
+        Ok(data) => data,
+        Err(_) => return Ok(None),
+    };
+    let mut db: HashMap<String, CachedModel> = match serde_json::from_slice(&data) {
+        Ok(db) => db,
+        Err(_) => return Ok(None),
+    };
+    Ok(db.remove(key))
+}
+
+/// Insert `key` -> a snapshot of `mg` into the cache database under `dir`,
+/// taking an exclusive lock for the duration so concurrent runs don't
+/// interleave writes, and swapping the new database in with a rename so
+/// readers never see a partially-written file.
+fn cache_store(dir: &Path, key: &str, mg: &MustGather) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(dir.join("camgi-cache.lock"))?;
+    lock.lock_exclusive()?;
+
+    let db_path = cache_db_path(dir);
+    let mut db: HashMap<String, CachedModel> = fs::read(&db_path)
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default();
+    db.insert(key.to_string(), CachedModel::from_mustgather(mg));
+
+    let tmp_path = dir.join(format!("camgi-cache.json.{}.tmp", std::process::id()));
+    fs::write(&tmp_path, serde_json::to_vec(&db)?)?;
+    fs::rename(&tmp_path, &db_path)?;
+
+    lock.unlock()?;
+    Ok(())
 }
 
 /// Build a path to a resource, does not guarantee that it exists.
@@ -92,14 +528,15 @@ fn find_must_gather_root(path: String) -> Result<PathBuf> {
     .collect();
 
     if vpath.is_file() || (npath.is_dir() && csrpath.is_dir()) {
-        return Ok(orig.canonicalize().unwrap());
+        return orig
+            .canonicalize()
+            .map_err(|e| anyhow::anyhow!("canonicalizing {}: {}", orig.display(), e));
     }
 
-    let directories: Vec<PathBuf> = fs::read_dir(orig)
-        .unwrap()
-        .into_iter()
-        .filter(|r| r.is_ok())
-        .map(|r| r.unwrap().path())
+    let directories: Vec<PathBuf> = fs::read_dir(&orig)
+        .map_err(|e| anyhow::anyhow!("reading directory {}: {}", orig.display(), e))?
+        .flatten()
+        .map(|r| r.path())
         .filter(|r| r.is_dir())
         .collect();
 
@@ -112,9 +549,9 @@ fn find_must_gather_root(path: String) -> Result<PathBuf> {
 
 /// Get the version string.
 /// If unable to determine the version, "Unknown" will be returned.
-fn get_cluster_version(path: &Path) -> String {
+fn get_cluster_version(ctx: &Context) -> String {
     let mut manifestpath =
-        build_manifest_path(path, "", "", "clusterversions", "config.openshift.io");
+        build_manifest_path(&ctx.root, "", "", "clusterversions", "config.openshift.io");
     manifestpath.push("version.yaml");
     let version = match Manifest::from(manifestpath) {
         Ok(v) => v,
@@ -126,25 +563,18 @@ fn get_cluster_version(path: &Path) -> String {
     }
 }
 
-/// Get all the Nodes in the cluster.
-fn get_nodes(path: &Path) -> Vec<Node> {
+/// Get all the Nodes in the cluster, reporting manifests that failed to
+/// parse as warnings rather than silently dropping them.
+fn get_nodes(ctx: &Context) -> (Vec<Node>, Vec<anyhow::Error>) {
     let mut nodes = Vec::new();
-    let manifestpath = build_manifest_path(path, "", "", "nodes", "core");
-    let yamlfiles: Vec<PathBuf> = fs::read_dir(&manifestpath)
-        .unwrap()
-        .into_iter()
-        .filter(|m| m.is_ok())
-        .map(|m| m.unwrap().path())
-        .filter(|m| m.extension().unwrap() == "yaml")
-        .collect();
-
-    for path in yamlfiles {
-        match Manifest::from(path) {
+    let mut warnings = Vec::new();
+    for path in ctx.manifests("", "nodes", "core") {
+        match Manifest::from(path.clone()) {
             Ok(m) => nodes.push(Node::from(m)),
-            Err(_) => continue,
+            Err(e) => warnings.push(e.context(format!("parsing {}", path.display()))),
         }
     }
-    nodes
+    (nodes, warnings)
 }
 
 #[cfg(test)]
@@ -199,22 +629,117 @@ mod tests {
 
     #[test]
     fn test_get_cluster_version() {
-        assert_eq!(
-            get_cluster_version(&PathBuf::from(
-                "testdata/must-gather-valid/sample-openshift-release"
-            )),
-            "X.Y.Z-fake-test"
-        )
+        let ctx = Context::new(PathBuf::from(
+            "testdata/must-gather-valid/sample-openshift-release",
+        ));
+        assert_eq!(get_cluster_version(&ctx), "X.Y.Z-fake-test")
     }
 
     #[test]
     fn test_get_nodes() {
+        let ctx = Context::new(PathBuf::from(
+            "testdata/must-gather-valid/sample-openshift-release",
+        ));
+        assert_eq!(get_nodes(&ctx).0.len(), 2)
+    }
+
+    #[test]
+    fn test_archive_encoding() {
+        assert_eq!(archive_encoding("must-gather.tar.gz"), Some(true));
+        assert_eq!(archive_encoding("must-gather.tgz"), Some(true));
+        assert_eq!(archive_encoding("must-gather.tar"), Some(false));
+        assert_eq!(archive_encoding("must-gather"), None);
+        assert_eq!(archive_encoding("must-gather.yaml"), None);
+    }
+
+    #[test]
+    fn test_resource_key() {
+        let base = PathBuf::from("/foo/cluster-scoped-resources");
+        assert_eq!(
+            resource_key(&base, &base.join("core/nodes/node1.yaml"), ""),
+            Some((String::from("core"), String::from("nodes"), String::new()))
+        );
+        assert_eq!(
+            resource_key(&base, &base.join("core/nodes/README"), ""),
+            None
+        );
+        assert_eq!(resource_key(&base, &base.join("core/nodes"), ""), None);
+    }
+
+    #[test]
+    fn test_extract_archive() {
+        let src = tempfile::tempdir().unwrap();
+        fs::write(src.path().join("version"), b"4.0.0-test\n").unwrap();
+
+        let archive_path = tempfile::Builder::new()
+            .suffix(".tar.gz")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        let archive = File::create(&archive_path).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            archive,
+            flate2::Compression::default(),
+        ));
+        builder.append_dir_all(".", src.path()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let extracted = extract_archive(archive_path.to_str().unwrap())
+            .unwrap()
+            .unwrap();
         assert_eq!(
-            get_nodes(&PathBuf::from(
-                "testdata/must-gather-valid/sample-openshift-release"
-            ))
-            .len(),
-            2
+            fs::read(extracted.path().join("version")).unwrap(),
+            b"4.0.0-test\n"
+        );
+
+        assert!(extract_archive("/not/an/archive").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_warnings_surfaces_manifest_parse_failure() {
+        let root = tempfile::tempdir().unwrap();
+        let nodes_dir = root.path().join("cluster-scoped-resources/core/nodes");
+        fs::create_dir_all(&nodes_dir).unwrap();
+        // Unterminated flow sequence: fails to parse as YAML.
+        fs::write(nodes_dir.join("broken.yaml"), "apiVersion: v1\nkind: [\n").unwrap();
+        fs::write(root.path().join("version"), b"").unwrap();
+
+        let mg = MustGather::build(String::from(root.path().to_str().unwrap())).unwrap();
+        mg.resources();
+        assert!(!mg.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_resources() {
+        let root = tempfile::tempdir().unwrap();
+        let nodes_dir = root.path().join("cluster-scoped-resources/core/nodes");
+        fs::create_dir_all(&nodes_dir).unwrap();
+        fs::write(
+            nodes_dir.join("node1.yaml"),
+            "apiVersion: v1\nkind: Node\nmetadata:\n  name: node1\n",
         )
+        .unwrap();
+        fs::write(root.path().join("version"), b"").unwrap();
+
+        let mg = MustGather::build(String::from(root.path().to_str().unwrap())).unwrap();
+        let key = (String::from("core"), String::from("nodes"), String::new());
+        assert_eq!(mg.resources().get(&key).map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let mg = MustGather::build(String::from(
+            "testdata/must-gather-valid/sample-openshift-release",
+        ))
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        cache_store(dir.path(), "test-key", &mg).unwrap();
+
+        let cached = cache_load(dir.path(), "test-key").unwrap().unwrap();
+        assert_eq!(cached.title, mg.title);
+        assert_eq!(cached.nodes.len(), mg.nodes().len());
+
+        assert!(cache_load(dir.path(), "other-key").unwrap().is_none());
     }
 }